@@ -1,12 +1,22 @@
-use std::{collections::HashMap, net::Ipv4Addr, str::FromStr};
+use std::{
+    collections::HashMap,
+    io,
+    net::{IpAddr, Ipv4Addr},
+    rc::Rc,
+    str::FromStr,
+};
 
 use boa_engine::{object::FunctionBuilder, property::Attribute, Context, JsResult, JsValue};
+use chrono::{Datelike, Local, NaiveDate, Timelike, Utc, Weekday};
 use gc::{Finalize, Trace};
-use ipnet::Ipv4Net;
-use local_ip_address::local_ip;
+use ipnet::{IpNet, Ipv4Net};
+use local_ip_address::list_afinet_netifas;
 use regex::Regex;
 use url::Url;
 
+#[cfg(feature = "reqwest")]
+pub mod reqwest;
+
 trait JsResultExt<T> {
     fn to_string(self, ctx: &mut Context) -> std::result::Result<T, Error>;
 }
@@ -25,6 +35,8 @@ impl<T> JsResultExt<T> for JsResult<T> {
 
 pub struct PacParser {
     js_ctx: Context,
+    resolver: Rc<dyn Resolver>,
+    bypass: Bypass,
 }
 
 pub struct PacFile<'ctx> {
@@ -41,6 +53,9 @@ pub enum Error {
     InvalidPacReturn,
     #[error("Url has no host")]
     NoHost,
+    #[cfg(feature = "reqwest")]
+    #[error("reqwest error: {0}")]
+    Reqwest(#[from] ::reqwest::Error),
 }
 
 type Result<T, E = Error> = std::result::Result<T, E>;
@@ -81,6 +96,34 @@ impl FromStr for ProxyType {
     }
 }
 
+/// Looks up addresses for PAC hostnames and the addresses of the local
+/// machine, on behalf of `dnsResolve`-family and `myIpAddress`-family
+/// builtins.
+///
+/// The default implementation, [`SystemResolver`], talks to the real
+/// system resolver and network stack. Implement this trait to plug in a
+/// cache, an async resolver driven off a channel, or a mock for tests.
+pub trait Resolver {
+    fn resolve(&self, host: &str) -> io::Result<Vec<IpAddr>>;
+    fn my_ip_addresses(&self) -> io::Result<Vec<IpAddr>>;
+}
+
+struct SystemResolver;
+
+impl Resolver for SystemResolver {
+    fn resolve(&self, host: &str) -> io::Result<Vec<IpAddr>> {
+        dns_lookup::lookup_host(host).map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+    }
+
+    fn my_ip_addresses(&self) -> io::Result<Vec<IpAddr>> {
+        Ok(list_afinet_netifas()
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?
+            .into_iter()
+            .map(|(_, ip)| ip)
+            .collect())
+    }
+}
+
 fn dns_domain_is(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
     match args {
         [a, b] => {
@@ -107,86 +150,449 @@ fn is_plain_hostname(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResu
     }
 }
 
-fn is_in_inet(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+fn sort_ip_address_list(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
     match args {
-        [host, net, mask] => {
-            let net: Ipv4Addr = net
-                .to_string(ctx)?
-                .parse()
-                .map_err(|err| format!("invalid ip addr: {err:?}"))?;
-
-            let mask: Ipv4Addr = mask
-                .to_string(ctx)?
-                .parse()
-                .map_err(|err| format!("invalid ip mask: {err:?}"))?;
-            let prefix_len = u32::from_ne_bytes(mask.octets()).count_ones();
-
-            let net = Ipv4Net::new(net, prefix_len as u8).expect("prefix should not be a problem");
-
-            match host.to_string(ctx)?.parse() {
-                Err(_) => {
-                    let ip = dns_resolve(host, &[host.clone()], ctx)?
-                        .to_string(ctx)?
-                        .parse()
-                        .expect("dns resolve should return an ip");
+        [list] => {
+            let list = list.to_string(ctx)?;
+            let mut addrs = list
+                .split(';')
+                .map(|s| {
+                    s.trim()
+                        .parse::<IpAddr>()
+                        .map_err(|err| format!("invalid ip address `{s}`: {err:?}"))
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+
+            addrs.sort_by_key(|ip| (ip.is_ipv4(), *ip));
+
+            Ok(addrs
+                .iter()
+                .map(IpAddr::to_string)
+                .collect::<Vec<_>>()
+                .join(";")
+                .into())
+        }
+        _ => unreachable!("expected one argument"),
+    }
+}
 
-                    Ok(net.contains::<&Ipv4Addr>(&ip).into())
-                }
-                Ok(ip) => Ok(net.contains::<&Ipv4Addr>(&ip).into()),
-            }
+fn local_host_or_domain_is(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    match args {
+        [a, b] => {
+            let (a, b) = (a.to_string(ctx)?, b.to_string(ctx)?);
+
+            Ok(b.starts_with(&*a).into())
         }
-        _ => unreachable!("expected three arguments"),
+        _ => unreachable!("expected two arguments"),
     }
 }
 
-fn dns_resolve(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+fn dns_domain_levels(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
     match args {
-        [name] => {
-            let lookup = dns_lookup::lookup_host(&name.to_string(ctx)?)
-                .map_err(|err| format!("dns error: {err:?}"))?;
+        [host] => Ok((host.to_string(ctx)?.matches('.').count() as i32).into()),
+        _ => unreachable!("expected one argument"),
+    }
+}
 
-            let v4 = lookup.iter().find(|ip| ip.is_ipv4());
+fn args_to_strings(args: &[JsValue], ctx: &mut Context) -> JsResult<Vec<String>> {
+    args.iter()
+        .map(|a| a.to_string(ctx).map(|s| s.to_string()))
+        .collect()
+}
 
-            match v4 {
-                None => todo!("handle ipv6"),
-                Some(v4) => Ok(v4.to_string().into()),
-            }
+/// Pops a trailing case-insensitive `"GMT"` marker off `args`, returning
+/// whether it was present.
+fn strip_gmt(args: &mut Vec<String>) -> bool {
+    match args.last() {
+        Some(last) if last.eq_ignore_ascii_case("GMT") => {
+            args.pop();
+            true
         }
-        _ => unreachable!("expected one argument"),
+        _ => false,
+    }
+}
+
+fn parse_weekday(s: &str) -> std::result::Result<Weekday, String> {
+    match s.to_uppercase().as_str() {
+        "SUN" => Ok(Weekday::Sun),
+        "MON" => Ok(Weekday::Mon),
+        "TUE" => Ok(Weekday::Tue),
+        "WED" => Ok(Weekday::Wed),
+        "THU" => Ok(Weekday::Thu),
+        "FRI" => Ok(Weekday::Fri),
+        "SAT" => Ok(Weekday::Sat),
+        _ => Err(format!("`{s}` is not a weekday code")),
     }
 }
 
-fn my_ip(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
-    let my_ip = local_ip().map_err(|err| format!("Could not get IP addr: {err:?}"))?;
+fn in_weekday_range(day: Weekday, start: Weekday, end: Weekday) -> bool {
+    let day = day.num_days_from_sunday();
+    let start = start.num_days_from_sunday();
+    let end = end.num_days_from_sunday();
 
-    Ok(my_ip.to_string().into())
+    if start <= end {
+        (start..=end).contains(&day)
+    } else {
+        day >= start || day <= end
+    }
 }
 
-fn local_host_or_domain_is(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
-    match args {
-        [a, b] => {
-            let (a, b) = (a.to_string(ctx)?, b.to_string(ctx)?);
+fn weekday_range(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let mut args = args_to_strings(args, ctx)?;
+    let gmt = strip_gmt(&mut args);
+    let today = if gmt {
+        Utc::now().weekday()
+    } else {
+        Local::now().weekday()
+    };
+
+    match args.as_slice() {
+        [wd1] => Ok((today == parse_weekday(wd1)?).into()),
+        [wd1, wd2] => Ok(in_weekday_range(today, parse_weekday(wd1)?, parse_weekday(wd2)?).into()),
+        _ => Err("weekdayRange: expected one or two weekday codes".to_string().into()),
+    }
+}
 
-            Ok(b.starts_with(&*a).into())
+fn month_from_name(s: &str) -> Option<u32> {
+    const MONTHS: &[&str] = &[
+        "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+    ];
+    MONTHS
+        .iter()
+        .position(|m| m.eq_ignore_ascii_case(s))
+        .map(|i| i as u32 + 1)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DatePart {
+    Day(u32),
+    Month(u32),
+    Year(i32),
+}
+
+fn parse_date_part(s: &str) -> std::result::Result<DatePart, String> {
+    if let Some(month) = month_from_name(s) {
+        return Ok(DatePart::Month(month));
+    }
+
+    let n: i64 = s
+        .parse()
+        .map_err(|_| format!("`{s}` is not a day, month name, or year"))?;
+
+    if n >= 1000 {
+        Ok(DatePart::Year(n as i32))
+    } else if (1..=31).contains(&n) {
+        Ok(DatePart::Day(n as u32))
+    } else {
+        Err(format!("`{s}` is out of range for a day or year"))
+    }
+}
+
+fn date_part_value(today: NaiveDate, part: DatePart) -> i64 {
+    match part {
+        DatePart::Day(_) => today.day() as i64,
+        DatePart::Month(_) => today.month() as i64,
+        DatePart::Year(_) => today.year() as i64,
+    }
+}
+
+fn date_part_raw(part: DatePart) -> i64 {
+    match part {
+        DatePart::Day(d) => d as i64,
+        DatePart::Month(m) => m as i64,
+        DatePart::Year(y) => y as i64,
+    }
+}
+
+fn in_date_part_range(today: NaiveDate, start: DatePart, end: DatePart) -> bool {
+    let value = date_part_value(today, start);
+    let start = date_part_raw(start);
+    let end = date_part_raw(end);
+
+    if start <= end {
+        (start..=end).contains(&value)
+    } else {
+        // A reversed range wraps around, e.g. months across a year boundary
+        // ("NOV", "FEB") or days across a month boundary (25, 5).
+        value >= start || value <= end
+    }
+}
+
+fn date_from_parts(parts: &[DatePart]) -> std::result::Result<NaiveDate, String> {
+    let mut day = None;
+    let mut month = None;
+    let mut year = None;
+
+    for part in parts {
+        match part {
+            DatePart::Day(d) => day = Some(*d),
+            DatePart::Month(m) => month = Some(*m),
+            DatePart::Year(y) => year = Some(*y),
         }
-        _ => unreachable!("expected two arguments"),
+    }
+
+    NaiveDate::from_ymd_opt(
+        year.ok_or("dateRange: missing year")?,
+        month.ok_or("dateRange: missing month")?,
+        day.ok_or("dateRange: missing day")?,
+    )
+    .ok_or_else(|| "dateRange: invalid date".to_string())
+}
+
+/// Builds a full date from two [`DatePart`]s (as used by `dateRange`'s
+/// `(day1, month1, day2, month2)` and `(month1, year1, month2, year2)`
+/// overloads), filling whichever component isn't given from `today`.
+fn date_from_two_parts(parts: &[DatePart], today: NaiveDate) -> std::result::Result<NaiveDate, String> {
+    let mut day = None;
+    let mut month = None;
+    let mut year = None;
+
+    for part in parts {
+        match part {
+            DatePart::Day(d) => day = Some(*d),
+            DatePart::Month(m) => month = Some(*m),
+            DatePart::Year(y) => year = Some(*y),
+        }
+    }
+
+    NaiveDate::from_ymd_opt(
+        year.unwrap_or_else(|| today.year()),
+        month.unwrap_or_else(|| today.month()),
+        day.unwrap_or_else(|| today.day()),
+    )
+    .ok_or_else(|| "dateRange: invalid date".to_string())
+}
+
+fn date_range(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let mut args = args_to_strings(args, ctx)?;
+    let gmt = strip_gmt(&mut args);
+    let today = if gmt {
+        Utc::now().date_naive()
+    } else {
+        Local::now().date_naive()
+    };
+
+    let parts = args
+        .iter()
+        .map(|s| parse_date_part(s))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    match parts.as_slice() {
+        [part] => Ok((date_part_value(today, *part) == date_part_raw(*part)).into()),
+        [start, end] => Ok(in_date_part_range(today, *start, *end).into()),
+        [a, b, c] => Ok((today == date_from_parts(&[*a, *b, *c])?).into()),
+        [a, b, c, d] => {
+            let start = date_from_two_parts(&[*a, *b], today)?;
+            let end = date_from_two_parts(&[*c, *d], today)?;
+
+            if start <= end {
+                Ok((start <= today && today <= end).into())
+            } else {
+                // The range wraps a year boundary, e.g. 25 Dec .. 5 Jan.
+                Ok((today >= start || today <= end).into())
+            }
+        }
+        [a, b, c, d, e, f] => {
+            let start = date_from_parts(&[*a, *b, *c])?;
+            let end = date_from_parts(&[*d, *e, *f])?;
+            Ok((start <= today && today <= end).into())
+        }
+        _ => Err("dateRange: unsupported combination of arguments".to_string().into()),
+    }
+}
+
+fn parse_time_part(s: &str) -> std::result::Result<u32, String> {
+    s.parse().map_err(|_| format!("`{s}` is not a number"))
+}
+
+fn seconds_since_midnight(hour: u32, minute: u32, second: u32) -> i64 {
+    (hour as i64 * 3600) + (minute as i64 * 60) + second as i64
+}
+
+/// Matches the Navigator PAC spec's `timeRange` semantics: the end bound is
+/// exclusive, e.g. `timeRange(9, 17)` is true for `[09:00:00, 17:00:00)`.
+fn in_time_range(now: i64, start: i64, end: i64) -> bool {
+    if start < end {
+        now >= start && now < end
+    } else {
+        now >= start || now < end
+    }
+}
+
+fn time_range(_: &JsValue, args: &[JsValue], ctx: &mut Context) -> JsResult<JsValue> {
+    let mut args = args_to_strings(args, ctx)?;
+    let gmt = strip_gmt(&mut args);
+    let now = if gmt { Utc::now().time() } else { Local::now().time() };
+    let now = seconds_since_midnight(now.hour(), now.minute(), now.second());
+
+    let parts = args
+        .iter()
+        .map(|s| parse_time_part(s))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    match parts.as_slice() {
+        [hour] => Ok((now / 3600 == *hour as i64).into()),
+        [hour1, hour2] => Ok(in_time_range(now / 3600, *hour1 as i64, *hour2 as i64).into()),
+        [hour1, min1, hour2, min2] => Ok(in_time_range(
+            now / 60,
+            (*hour1 as i64) * 60 + *min1 as i64,
+            (*hour2 as i64) * 60 + *min2 as i64,
+        )
+        .into()),
+        [hour1, min1, sec1, hour2, min2, sec2] => Ok(in_time_range(
+            now,
+            seconds_since_midnight(*hour1, *min1, *sec1),
+            seconds_since_midnight(*hour2, *min2, *sec2),
+        )
+        .into()),
+        _ => Err("timeRange: unsupported combination of arguments".to_string().into()),
     }
 }
 
+#[derive(Trace, Finalize)]
+struct ResolverHandle {
+    #[unsafe_ignore_trace]
+    resolver: Rc<dyn Resolver>,
+}
+
+impl ResolverHandle {
+    fn resolve(&self, host: &str) -> std::result::Result<Vec<IpAddr>, String> {
+        self.resolver
+            .resolve(host)
+            .map_err(|err| format!("dns error: {err}"))
+    }
+
+    fn my_ip_addresses(&self) -> std::result::Result<Vec<IpAddr>, String> {
+        self.resolver
+            .my_ip_addresses()
+            .map_err(|err| format!("Could not list interfaces: {err}"))
+    }
+}
+
+/// Controls how `shExpMatch` interprets its pattern argument.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    /// Shell-glob semantics mandated by the PAC spec: `*` matches any
+    /// sequence, `?` matches a single character, everything else is
+    /// literal.
+    #[default]
+    Glob,
+    /// Legacy behavior: the pattern is compiled as a regex directly, for
+    /// PAC files that (non-standardly) rely on it.
+    Regex,
+}
+
+#[derive(Debug, Clone)]
+enum BypassRule {
+    /// `*`: bypass the proxy for every request.
+    Wildcard,
+    /// An exact hostname match.
+    Exact(String),
+    /// A domain suffix such as `.internal`.
+    Suffix(String),
+    /// A CIDR block such as `10.0.0.0/8`.
+    Cidr(IpNet),
+}
+
+/// A static `NO_PROXY`-style bypass list: hosts matching one of its rules
+/// short-circuit [`PacFile::find_proxy`] to `DIRECT` without evaluating the
+/// PAC script.
+#[derive(Debug, Clone, Default)]
+pub struct Bypass {
+    rules: Vec<BypassRule>,
+}
+
+impl Bypass {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a bypass list from the conventional `NO_PROXY`/`no_proxy`
+    /// environment variables: a comma-separated list of exact hostnames,
+    /// `.`-prefixed domain suffixes, CIDR blocks, or `*`. Empty when
+    /// neither variable is set.
+    pub fn from_env() -> Self {
+        let value = std::env::var("NO_PROXY")
+            .or_else(|_| std::env::var("no_proxy"))
+            .unwrap_or_default();
+
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|rule| !rule.is_empty())
+            .fold(Self::new(), Self::rule)
+    }
+
+    /// Adds a single rule: `*`, an exact hostname, a `.`-prefixed domain
+    /// suffix, or a CIDR block.
+    pub fn rule(mut self, rule: &str) -> Self {
+        let rule = rule.trim();
+        let parsed = if rule == "*" {
+            BypassRule::Wildcard
+        } else if let Ok(net) = rule.parse::<IpNet>() {
+            BypassRule::Cidr(net)
+        } else if let Some(suffix) = rule.strip_prefix('.') {
+            BypassRule::Suffix(suffix.to_lowercase())
+        } else {
+            BypassRule::Exact(rule.to_lowercase())
+        };
+
+        self.rules.push(parsed);
+        self
+    }
+
+    fn matches(&self, host: &str, resolver: &Rc<dyn Resolver>) -> bool {
+        let host = host.to_lowercase();
+        let mut ip = host.parse::<IpAddr>().ok();
+
+        self.rules.iter().any(|rule| match rule {
+            BypassRule::Wildcard => true,
+            BypassRule::Exact(exact) => *exact == host,
+            BypassRule::Suffix(suffix) => {
+                host == *suffix || host.ends_with(&format!(".{suffix}"))
+            }
+            BypassRule::Cidr(net) => {
+                if ip.is_none() {
+                    ip = resolver
+                        .resolve(&host)
+                        .ok()
+                        .and_then(|addrs| addrs.into_iter().next());
+                }
+                ip.is_some_and(|ip| net.contains(&ip))
+            }
+        })
+    }
+}
+
+/// Translates a shell glob into an anchored regex source, escaping every
+/// regex metacharacter first so only `*` and `?` act as wildcards.
+fn glob_to_regex(glob: &str) -> String {
+    let escaped = regex::escape(glob);
+    let translated = escaped.replace(r"\*", ".*").replace(r"\?", ".");
+
+    format!("^{translated}$")
+}
+
 #[derive(Trace, Finalize, Debug)]
 struct RegexCache {
     #[unsafe_ignore_trace]
     cache: HashMap<String, Regex>,
+    #[unsafe_ignore_trace]
+    mode: MatchMode,
 }
 
 impl RegexCache {
-    fn matches(&mut self, str: &str, regex: &str) -> JsResult<bool> {
-        match self.cache.get(regex) {
+    fn matches(&mut self, str: &str, pattern: &str) -> JsResult<bool> {
+        match self.cache.get(pattern) {
             None => {
-                let re = Regex::new(&format!("^{regex}$"))
-                    .map_err(|err| format!("regex error: {err:?}"))?;
+                let source = match self.mode {
+                    MatchMode::Glob => glob_to_regex(pattern),
+                    MatchMode::Regex => format!("^{pattern}$"),
+                };
+                let re =
+                    Regex::new(&source).map_err(|err| format!("regex error: {err:?}"))?;
                 let is_match = re.is_match(str);
-                self.cache.insert(regex.into(), re);
+                self.cache.insert(pattern.into(), re);
                 Ok(is_match)
             }
             Some(re) => Ok(re.is_match(str)),
@@ -196,17 +602,235 @@ impl RegexCache {
 
 impl PacParser {
     pub fn new() -> Result<Self> {
+        Self::with_options(SystemResolver, MatchMode::default())
+    }
+
+    /// Builds a parser whose `dnsResolve`/`myIpAddress`-family builtins are
+    /// backed by `resolver` instead of the real system resolver. Useful for
+    /// deterministic tests or to inject a caching/async resolver.
+    pub fn with_resolver<R: Resolver + 'static>(resolver: R) -> Result<Self> {
+        Self::with_options(resolver, MatchMode::default())
+    }
+
+    /// Builds a parser whose `shExpMatch` interprets its pattern according
+    /// to `match_mode` instead of the default shell-glob semantics.
+    pub fn with_match_mode(match_mode: MatchMode) -> Result<Self> {
+        Self::with_options(SystemResolver, match_mode)
+    }
+
+    /// Replaces the bypass list consulted by [`PacFile::find_proxy`] before
+    /// the PAC script runs. Defaults to [`Bypass::from_env`].
+    pub fn with_bypass(mut self, bypass: Bypass) -> Self {
+        self.bypass = bypass;
+        self
+    }
+
+    /// Builds a parser with both a custom [`Resolver`] and [`MatchMode`].
+    pub fn with_options<R: Resolver + 'static>(resolver: R, match_mode: MatchMode) -> Result<Self> {
         let mut js_ctx = Context::builder().build();
+        let resolver: Rc<dyn Resolver> = Rc::new(resolver);
+        let handle = || ResolverHandle {
+            resolver: Rc::clone(&resolver),
+        };
 
         js_ctx.register_global_builtin_function("dnsDomainIs", 2, dns_domain_is);
         js_ctx.register_global_builtin_function("isPlainHostName", 1, is_plain_hostname);
-        js_ctx.register_global_builtin_function("isInNet", 3, is_in_inet);
-        js_ctx.register_global_builtin_function("dnsResolve", 1, dns_resolve);
-        js_ctx.register_global_builtin_function("myIpAddress", 0, my_ip);
         js_ctx.register_global_builtin_function("localHostOrDomainIs", 2, local_host_or_domain_is);
+        js_ctx.register_global_builtin_function("sortIpAddressList", 1, sort_ip_address_list);
+        js_ctx.register_global_builtin_function("dnsDomainLevels", 1, dns_domain_levels);
+        js_ctx.register_global_builtin_function("weekdayRange", 3, weekday_range);
+        js_ctx.register_global_builtin_function("dateRange", 7, date_range);
+        js_ctx.register_global_builtin_function("timeRange", 7, time_range);
+
+        let is_in_net = FunctionBuilder::closure_with_captures(
+            &mut js_ctx,
+            |_, args, resolver: &mut ResolverHandle, ctx| match args {
+                [host, net, mask] => {
+                    let net: Ipv4Addr = net
+                        .to_string(ctx)?
+                        .parse()
+                        .map_err(|err| format!("invalid ip addr: {err:?}"))?;
+
+                    let mask: Ipv4Addr = mask
+                        .to_string(ctx)?
+                        .parse()
+                        .map_err(|err| format!("invalid ip mask: {err:?}"))?;
+                    let prefix_len = u32::from_ne_bytes(mask.octets()).count_ones();
+
+                    let net = Ipv4Net::new(net, prefix_len as u8)
+                        .expect("prefix should not be a problem");
+
+                    let host = host.to_string(ctx)?;
+                    let ip: Ipv4Addr = match host.parse() {
+                        Ok(ip) => ip,
+                        Err(_) => resolver
+                            .resolve(&host)?
+                            .into_iter()
+                            .find_map(|ip| match ip {
+                                IpAddr::V4(v4) => Some(v4),
+                                IpAddr::V6(_) => None,
+                            })
+                            .ok_or_else(|| {
+                                format!("`{host}` did not resolve to an IPv4 address")
+                            })?,
+                    };
+
+                    Ok(net.contains::<&Ipv4Addr>(&ip).into())
+                }
+                _ => unreachable!("expected three arguments"),
+            },
+            handle(),
+        )
+        .length(3)
+        .name("isInNet")
+        .build();
+        js_ctx.register_global_property("isInNet", is_in_net, Attribute::all());
+
+        let dns_resolve = FunctionBuilder::closure_with_captures(
+            &mut js_ctx,
+            |_, args, resolver: &mut ResolverHandle, ctx| match args {
+                [name] => {
+                    let host = name.to_string(ctx)?;
+                    let addrs = resolver.resolve(&host)?;
+                    let first = addrs
+                        .iter()
+                        .find(|ip| ip.is_ipv4())
+                        .or_else(|| addrs.first())
+                        .ok_or_else(|| format!("`{host}` did not resolve to any address"))?;
+
+                    Ok(first.to_string().into())
+                }
+                _ => unreachable!("expected one argument"),
+            },
+            handle(),
+        )
+        .length(1)
+        .name("dnsResolve")
+        .build();
+        js_ctx.register_global_property("dnsResolve", dns_resolve, Attribute::all());
+
+        let my_ip = FunctionBuilder::closure_with_captures(
+            &mut js_ctx,
+            |_, _, resolver: &mut ResolverHandle, _| {
+                let addrs = resolver.my_ip_addresses()?;
+                // Prefer a routable address over loopback, which is
+                // typically the first interface returned on Linux.
+                let ip = addrs
+                    .iter()
+                    .find(|ip| !ip.is_loopback())
+                    .or_else(|| addrs.first())
+                    .ok_or_else(|| "no local ip address found".to_string())?;
+
+                Ok(ip.to_string().into())
+            },
+            handle(),
+        )
+        .length(0)
+        .name("myIpAddress")
+        .build();
+        js_ctx.register_global_property("myIpAddress", my_ip, Attribute::all());
+
+        let dns_resolve_ex = FunctionBuilder::closure_with_captures(
+            &mut js_ctx,
+            |_, args, resolver: &mut ResolverHandle, ctx| match args {
+                [name] => {
+                    let addrs = resolver.resolve(&name.to_string(ctx)?)?;
+                    Ok(addrs
+                        .iter()
+                        .map(IpAddr::to_string)
+                        .collect::<Vec<_>>()
+                        .join(";")
+                        .into())
+                }
+                _ => unreachable!("expected one argument"),
+            },
+            handle(),
+        )
+        .length(1)
+        .name("dnsResolveEx")
+        .build();
+        js_ctx.register_global_property("dnsResolveEx", dns_resolve_ex, Attribute::all());
+
+        let my_ip_ex = FunctionBuilder::closure_with_captures(
+            &mut js_ctx,
+            |_, _, resolver: &mut ResolverHandle, _| {
+                let addrs = resolver.my_ip_addresses()?;
+                Ok(addrs
+                    .iter()
+                    .map(IpAddr::to_string)
+                    .collect::<Vec<_>>()
+                    .join(";")
+                    .into())
+            },
+            handle(),
+        )
+        .length(0)
+        .name("myIpAddressEx")
+        .build();
+        js_ctx.register_global_property("myIpAddressEx", my_ip_ex, Attribute::all());
+
+        let is_in_net_ex = FunctionBuilder::closure_with_captures(
+            &mut js_ctx,
+            |_, args, resolver: &mut ResolverHandle, ctx| match args {
+                [ip_address, ip_prefix] => {
+                    let prefix: IpNet = ip_prefix
+                        .to_string(ctx)?
+                        .parse()
+                        .map_err(|err| format!("invalid CIDR prefix: {err:?}"))?;
+
+                    let host = ip_address.to_string(ctx)?;
+                    let ip: IpAddr = match host.parse() {
+                        Ok(ip) => ip,
+                        Err(_) => *resolver
+                            .resolve(&host)?
+                            .first()
+                            .ok_or_else(|| format!("could not resolve `{host}`"))?,
+                    };
+
+                    Ok(prefix.contains(&ip).into())
+                }
+                _ => unreachable!("expected two arguments"),
+            },
+            handle(),
+        )
+        .length(2)
+        .name("isInNetEx")
+        .build();
+        js_ctx.register_global_property("isInNetEx", is_in_net_ex, Attribute::all());
+
+        let is_resolvable_ex = FunctionBuilder::closure_with_captures(
+            &mut js_ctx,
+            |_, args, resolver: &mut ResolverHandle, ctx| match args {
+                [name] => Ok((!resolver.resolve(&name.to_string(ctx)?)?.is_empty()).into()),
+                _ => unreachable!("expected one argument"),
+            },
+            handle(),
+        )
+        .length(1)
+        .name("isResolvableEx")
+        .build();
+        js_ctx.register_global_property("isResolvableEx", is_resolvable_ex, Attribute::all());
+
+        let is_resolvable = FunctionBuilder::closure_with_captures(
+            &mut js_ctx,
+            |_, args, resolver: &mut ResolverHandle, ctx| match args {
+                [name] => Ok(resolver
+                    .resolve(&name.to_string(ctx)?)?
+                    .iter()
+                    .any(IpAddr::is_ipv4)
+                    .into()),
+                _ => unreachable!("expected one argument"),
+            },
+            handle(),
+        )
+        .length(1)
+        .name("isResolvable")
+        .build();
+        js_ctx.register_global_property("isResolvable", is_resolvable, Attribute::all());
 
         let cache = RegexCache {
             cache: HashMap::new(),
+            mode: match_mode,
         };
 
         let sh_exp = FunctionBuilder::closure_with_captures(
@@ -224,7 +848,11 @@ impl PacParser {
         .build();
         js_ctx.register_global_property("shExpMatch", sh_exp, Attribute::all());
 
-        Ok(Self { js_ctx })
+        Ok(Self {
+            js_ctx,
+            resolver,
+            bypass: Bypass::from_env(),
+        })
     }
 
     pub fn load<D: AsRef<str>>(&mut self, file: D) -> Result<PacFile> {
@@ -243,6 +871,10 @@ impl<'ctx> PacFile<'ctx> {
     pub fn find_proxy(&mut self, url: &Url) -> Result<Vec<ProxyEntry>> {
         let host = url.host_str().ok_or(Error::NoHost)?;
 
+        if self.ctx.bypass.matches(host, &self.ctx.resolver) {
+            return Ok(vec![ProxyEntry::Direct]);
+        }
+
         let pac = self
             .ctx
             .js_ctx
@@ -267,22 +899,20 @@ impl<'ctx> PacFile<'ctx> {
             .split(';')
             .map(|part| {
                 let part = part.trim();
-                if let Some(x) = part.strip_prefix("DIRECT") {
-                    assert!(x.trim().is_empty(), "DIRECT with host is not supported");
+                if part.starts_with("DIRECT") {
                     Ok(ProxyEntry::Direct)
                 } else {
-                    let types = &["PROXY", "SOCKS", "HTTP", "HTTPS", "SOCKS4", "SOCKS5"];
+                    // Longest-prefix-first so e.g. "SOCKS5"/"SOCKS4" win over
+                    // the "SOCKS" prefix they both start with, and "HTTPS"
+                    // wins over "HTTP".
+                    let types = &["SOCKS4", "SOCKS5", "HTTPS", "PROXY", "SOCKS", "HTTP"];
                     for ty in types {
                         if let Some(proxy) = part.strip_prefix(ty) {
-                            let proxy = proxy.trim();
-                            let colon = proxy.find(':').ok_or_else(|| {
-                                Error::MalformedProxyEntry("No colon in entry".into())
-                            })?;
-                            let (host, port) = proxy.trim().split_at(colon);
+                            let (host, port) = parse_host_port(proxy.trim())?;
                             return Ok(ProxyEntry::Proxied {
                                 ty: ty.parse()?,
-                                host: host.into(),
-                                port: port[1..].into(),
+                                host,
+                                port,
                             });
                         }
                     }
@@ -293,6 +923,35 @@ impl<'ctx> PacFile<'ctx> {
     }
 }
 
+/// Splits a `host:port` proxy directive, accounting for bracketed IPv6
+/// literals (`[::1]:8080`) whose host portion may itself contain colons,
+/// and validates the host with `url`'s host parser so malformed addresses
+/// surface as [`Error::MalformedProxyEntry`].
+fn parse_host_port(proxy: &str) -> Result<(String, String)> {
+    let (host, port) = if let Some(rest) = proxy.strip_prefix('[') {
+        let end = rest
+            .find(']')
+            .ok_or_else(|| Error::MalformedProxyEntry("Unterminated IPv6 literal".into()))?;
+        let port = rest[end + 1..].strip_prefix(':').ok_or_else(|| {
+            Error::MalformedProxyEntry("No port after IPv6 literal".into())
+        })?;
+
+        (format!("[{}]", &rest[..end]), port)
+    } else {
+        let colon = proxy
+            .rfind(':')
+            .ok_or_else(|| Error::MalformedProxyEntry("No colon in entry".into()))?;
+        let (host, port) = proxy.split_at(colon);
+
+        (host.to_string(), &port[1..])
+    };
+
+    url::Host::parse(&host)
+        .map_err(|err| Error::MalformedProxyEntry(format!("invalid host `{host}`: {err}")))?;
+
+    Ok((host, port.to_string()))
+}
+
 impl<'ctx> Drop for PacFile<'ctx> {
     fn drop(&mut self) {
         if let Err(e) = self.ctx.js_ctx.eval("pac = undefined;") {
@@ -303,9 +962,43 @@ impl<'ctx> Drop for PacFile<'ctx> {
 
 #[cfg(test)]
 mod test {
+    use std::{
+        io,
+        net::{IpAddr, Ipv4Addr},
+    };
+
+    use chrono::NaiveDate;
     use url::Url;
 
-    use crate::{PacParser, ProxyEntry, ProxyType};
+    use crate::{Bypass, MatchMode, PacParser, ProxyEntry, ProxyType, Resolver};
+
+    /// A [`Resolver`] that always returns a fixed set of addresses, so DNS-
+    /// dependent builtins can be tested without touching the real network.
+    struct MockResolver(Vec<IpAddr>);
+
+    impl Resolver for MockResolver {
+        fn resolve(&self, _host: &str) -> io::Result<Vec<IpAddr>> {
+            Ok(self.0.clone())
+        }
+
+        fn my_ip_addresses(&self) -> io::Result<Vec<IpAddr>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    /// Builds a parser for tests that don't exercise bypass behavior
+    /// themselves. `PacParser::new` seeds its bypass list from the
+    /// `NO_PROXY`/`no_proxy` environment variables, which would otherwise
+    /// make these tests' results depend on the ambient environment.
+    fn test_parser() -> PacParser {
+        PacParser::new().unwrap().with_bypass(Bypass::new())
+    }
+
+    fn test_parser_with_match_mode(match_mode: MatchMode) -> PacParser {
+        PacParser::with_match_mode(match_mode)
+            .unwrap()
+            .with_bypass(Bypass::new())
+    }
 
     macro_rules! pac {
         ($code:literal) => {
@@ -328,24 +1021,24 @@ mod test {
 
     #[test]
     fn init_fini() {
-        PacParser::new().unwrap();
+        test_parser();
     }
 
     #[test]
     fn load_direct() {
-        let mut parser = PacParser::new().unwrap();
+        let mut parser = test_parser();
         parser.load(DIRECT).unwrap();
     }
 
     #[test]
     fn load_simple() {
-        let mut parser = PacParser::new().unwrap();
+        let mut parser = test_parser();
         parser.load(SIMPLE).unwrap();
     }
 
     #[test]
     fn run_direct() {
-        let mut parser = PacParser::new().unwrap();
+        let mut parser = test_parser();
         let mut pac = parser.load(DIRECT).unwrap();
         let proxy = pac
             .find_proxy(&Url::parse("http://localhost").unwrap())
@@ -356,12 +1049,126 @@ mod test {
 
     #[test]
     fn run_simple() {
-        let mut parser = PacParser::new().unwrap();
+        let mut parser = test_parser();
+        let mut pac = parser.load(SIMPLE).unwrap();
+        let proxy = pac
+            .find_proxy(&Url::parse("http://localhost").unwrap())
+            .unwrap();
+
+        assert_eq!(
+            proxy,
+            vec![
+                ProxyEntry::Proxied {
+                    ty: ProxyType::Proxy,
+                    host: "127.0.0.1".into(),
+                    port: "8118".into(),
+                },
+                ProxyEntry::Direct
+            ]
+        );
+    }
+
+    define_pac! {IPV6_PROXY, r#"return "PROXY [2001:db8::1]:8080";"#}
+    define_pac! {DIRECT_WITH_HOST, r#"return "DIRECT some.host";"#}
+
+    #[test]
+    fn run_ipv6_proxy() {
+        let mut parser = test_parser();
+        let mut pac = parser.load(IPV6_PROXY).unwrap();
+        let proxy = pac
+            .find_proxy(&Url::parse("http://localhost").unwrap())
+            .unwrap();
+
+        assert_eq!(
+            proxy,
+            vec![ProxyEntry::Proxied {
+                ty: ProxyType::Proxy,
+                host: "[2001:db8::1]".into(),
+                port: "8080".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn run_direct_with_trailing_token() {
+        let mut parser = test_parser();
+        let mut pac = parser.load(DIRECT_WITH_HOST).unwrap();
+        let proxy = pac
+            .find_proxy(&Url::parse("http://localhost").unwrap())
+            .unwrap();
+
+        assert_eq!(proxy, vec![ProxyEntry::Direct]);
+    }
+
+    define_pac! {SOCKS5_PROXY, r#"return "SOCKS5 1.2.3.4:1080";"#}
+
+    #[test]
+    fn run_socks5_proxy_is_not_shadowed_by_socks_prefix() {
+        let mut parser = test_parser();
+        let mut pac = parser.load(SOCKS5_PROXY).unwrap();
+        let proxy = pac
+            .find_proxy(&Url::parse("http://localhost").unwrap())
+            .unwrap();
+
+        assert_eq!(
+            proxy,
+            vec![ProxyEntry::Proxied {
+                ty: ProxyType::Socks5,
+                host: "1.2.3.4".into(),
+                port: "1080".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn bypass_exact_host_short_circuits() {
+        let mut parser = PacParser::new()
+            .unwrap()
+            .with_bypass(Bypass::new().rule("localhost"));
         let mut pac = parser.load(SIMPLE).unwrap();
         let proxy = pac
             .find_proxy(&Url::parse("http://localhost").unwrap())
             .unwrap();
 
+        assert_eq!(proxy, vec![ProxyEntry::Direct]);
+    }
+
+    #[test]
+    fn bypass_domain_suffix_short_circuits() {
+        let mut parser = PacParser::new()
+            .unwrap()
+            .with_bypass(Bypass::new().rule(".internal"));
+        let mut pac = parser.load(SIMPLE).unwrap();
+        let proxy = pac
+            .find_proxy(&Url::parse("http://host.internal").unwrap())
+            .unwrap();
+
+        assert_eq!(proxy, vec![ProxyEntry::Direct]);
+    }
+
+    #[test]
+    fn bypass_cidr_short_circuits() {
+        let mut parser = PacParser::new()
+            .unwrap()
+            .with_bypass(Bypass::new().rule("127.0.0.0/8"));
+        let mut pac = parser.load(SIMPLE).unwrap();
+        let proxy = pac
+            .find_proxy(&Url::parse("http://127.0.0.1").unwrap())
+            .unwrap();
+
+        assert_eq!(proxy, vec![ProxyEntry::Direct]);
+    }
+
+    #[test]
+    fn bypass_no_match_falls_through_to_pac() {
+        let mut parser = PacParser::new()
+            .unwrap()
+            .with_bypass(Bypass::new().rule("localhost"));
+        let mut pac = parser.load(SIMPLE).unwrap();
+        let proxy = pac
+            .find_proxy(&Url::parse("http://example.com").unwrap())
+            .unwrap();
+
         assert_eq!(
             proxy,
             vec![
@@ -375,11 +1182,138 @@ mod test {
         );
     }
 
+    #[test]
+    fn weekday_range_wraps_around_week_boundary() {
+        // FRI..MON wraps past SAT/SUN, so it must include every day except
+        // TUE, WED and THU.
+        assert!(crate::in_weekday_range(
+            chrono::Weekday::Fri,
+            chrono::Weekday::Fri,
+            chrono::Weekday::Mon
+        ));
+        assert!(crate::in_weekday_range(
+            chrono::Weekday::Sun,
+            chrono::Weekday::Fri,
+            chrono::Weekday::Mon
+        ));
+        assert!(crate::in_weekday_range(
+            chrono::Weekday::Mon,
+            chrono::Weekday::Fri,
+            chrono::Weekday::Mon
+        ));
+        assert!(!crate::in_weekday_range(
+            chrono::Weekday::Wed,
+            chrono::Weekday::Fri,
+            chrono::Weekday::Mon
+        ));
+    }
+
+    #[test]
+    fn date_range_month_wraps_around_year_boundary() {
+        // NOV..FEB wraps past the year end, so it must include DEC/JAN but
+        // not a summer month.
+        let today = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        assert!(crate::in_date_part_range(
+            today,
+            crate::DatePart::Month(11),
+            crate::DatePart::Month(2)
+        ));
+        let july = NaiveDate::from_ymd_opt(2026, 7, 15).unwrap();
+        assert!(!crate::in_date_part_range(
+            july,
+            crate::DatePart::Month(11),
+            crate::DatePart::Month(2)
+        ));
+    }
+
+    #[test]
+    fn date_range_day_month_pair_spans_full_date_range() {
+        // 1 JAN .. 5 MAR must include every day in between, including ones
+        // whose day-of-month (10) falls outside [1, 5].
+        let feb_10 = NaiveDate::from_ymd_opt(2026, 2, 10).unwrap();
+        let start = crate::date_from_two_parts(
+            &[crate::DatePart::Day(1), crate::DatePart::Month(1)],
+            feb_10,
+        )
+        .unwrap();
+        let end = crate::date_from_two_parts(
+            &[crate::DatePart::Day(5), crate::DatePart::Month(3)],
+            feb_10,
+        )
+        .unwrap();
+
+        assert!(start <= feb_10 && feb_10 <= end);
+    }
+
+    #[test]
+    fn time_range_wraps_around_midnight() {
+        let ten_pm = crate::seconds_since_midnight(22, 0, 0);
+        let one_fifty_nine_am = crate::seconds_since_midnight(1, 59, 59);
+        let two_am = crate::seconds_since_midnight(2, 0, 0);
+        let eleven_pm = crate::seconds_since_midnight(23, 0, 0);
+        let noon = crate::seconds_since_midnight(12, 0, 0);
+
+        assert!(crate::in_time_range(eleven_pm, ten_pm, two_am));
+        // The end bound is exclusive, so 01:59:59 is still in range but
+        // 02:00:00 itself is not.
+        assert!(crate::in_time_range(one_fifty_nine_am, ten_pm, two_am));
+        assert!(!crate::in_time_range(two_am, ten_pm, two_am));
+        assert!(!crate::in_time_range(noon, ten_pm, two_am));
+    }
+
+    define_pac! {WEEKDAY_GMT_TODAY, r#"return weekdayRange("SUN", "SAT", "GMT") ? "PROXY 1:80" : "DIRECT";"#}
+
+    #[test]
+    fn weekday_range_gmt_full_week_is_always_true() {
+        let mut parser = test_parser();
+        let mut pac = parser.load(WEEKDAY_GMT_TODAY).unwrap();
+        let proxy = pac
+            .find_proxy(&Url::parse("http://localhost").unwrap())
+            .unwrap();
+
+        assert_eq!(
+            proxy,
+            vec![ProxyEntry::Proxied {
+                ty: ProxyType::Proxy,
+                host: "1".into(),
+                port: "80".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn mock_resolver_drives_is_in_net_and_dns_resolve() {
+        let mut parser = PacParser::with_resolver(MockResolver(vec![IpAddr::V4(Ipv4Addr::new(
+            10, 1, 2, 3,
+        ))]))
+        .unwrap()
+        .with_bypass(Bypass::new());
+        let pac = pac!(
+            r#"return isInNet(dnsResolve(host), "10.0.0.0", "255.0.0.0") ? "PROXY 1:80" : "DIRECT";"#
+        );
+        let mut pac = parser.load(pac).unwrap();
+        let proxy = pac
+            .find_proxy(&Url::parse("http://example.com").unwrap())
+            .unwrap();
+
+        assert_eq!(
+            proxy,
+            vec![ProxyEntry::Proxied {
+                ty: ProxyType::Proxy,
+                host: "1".into(),
+                port: "80".into()
+            }]
+        );
+    }
+
     macro_rules! define_pac_test {
         ($name:ident, $condition:literal, $input:literal) => {
+            define_pac_test! { $name, $condition, $input, MatchMode::Glob }
+        };
+        ($name:ident, $condition:literal, $input:literal, $mode:expr) => {
             #[test]
             fn $name() {
-                let mut parser = PacParser::new().unwrap();
+                let mut parser = test_parser_with_match_mode($mode);
                 let pac = pac!(
                     r#"
                     if ({})
@@ -412,15 +1346,29 @@ mod test {
     define_pac_test! {
         sh_expr_exact,
         r#"shExpMatch(host, "(.*.adcdom.com|abcdom.com)")"#,
-        "http://abcdom.com"
+        "http://abcdom.com",
+        MatchMode::Regex
     }
 
     define_pac_test! {
         sh_expr_repeat,
         r#"shExpMatch(host, "(.*.abcdom.com|abcdom.com)")"#,
+        "http://foo.abcdom.com",
+        MatchMode::Regex
+    }
+
+    define_pac_test! {
+        sh_expr_glob_star,
+        r#"shExpMatch(host, "*.abcdom.com")"#,
         "http://foo.abcdom.com"
     }
 
+    define_pac_test! {
+        sh_expr_glob_dot_is_literal,
+        r#"!shExpMatch(host, "foo.abcdom.com") && shExpMatch(host, "fooxabcdom.com")"#,
+        "http://fooxabcdom.com"
+    }
+
     define_pac_test! {
         substring,
         r#"url.substring(0, 4) == "ftp:""#,
@@ -433,16 +1381,54 @@ mod test {
         "http://localhost"
     }
 
-    define_pac_test! {
-        is_in_net_resolve,
-        r#"isInNet(dnsResolve(host), "127.0.0.0", "255.0.0.0")"#,
-        "http://localhost"
+    #[test]
+    fn is_in_net_resolve() {
+        let mut parser = PacParser::with_resolver(MockResolver(vec![IpAddr::V4(Ipv4Addr::new(
+            127, 0, 0, 1,
+        ))]))
+        .unwrap()
+        .with_bypass(Bypass::new());
+        let pac = pac!(
+            r#"return isInNet(dnsResolve(host), "127.0.0.0", "255.0.0.0") ? "PROXY 1:80" : "DIRECT";"#
+        );
+        let mut pac = parser.load(pac).unwrap();
+        let proxy = pac
+            .find_proxy(&Url::parse("http://localhost").unwrap())
+            .unwrap();
+
+        assert_eq!(
+            proxy,
+            vec![ProxyEntry::Proxied {
+                ty: ProxyType::Proxy,
+                host: "1".into(),
+                port: "80".into()
+            }]
+        );
     }
 
-    define_pac_test! {
-        my_ip,
-        r#"isInNet(myIpAddress(), "192.168.0.0", "255.255.0.0")"#,
-        "http://localhost"
+    #[test]
+    fn my_ip() {
+        let mut parser = PacParser::with_resolver(MockResolver(vec![IpAddr::V4(Ipv4Addr::new(
+            192, 168, 1, 5,
+        ))]))
+        .unwrap()
+        .with_bypass(Bypass::new());
+        let pac = pac!(
+            r#"return isInNet(myIpAddress(), "192.168.0.0", "255.255.0.0") ? "PROXY 1:80" : "DIRECT";"#
+        );
+        let mut pac = parser.load(pac).unwrap();
+        let proxy = pac
+            .find_proxy(&Url::parse("http://localhost").unwrap())
+            .unwrap();
+
+        assert_eq!(
+            proxy,
+            vec![ProxyEntry::Proxied {
+                ty: ProxyType::Proxy,
+                host: "1".into(),
+                port: "80".into()
+            }]
+        );
     }
 
     define_pac_test! {
@@ -456,4 +1442,33 @@ mod test {
         r#"localHostOrDomainIs(host, "www.mozilla.org")"#,
         "http://www"
     }
+
+    define_pac_test! {
+        dns_domain_levels,
+        r#"dnsDomainLevels(host) == 2"#,
+        "http://www.example.com"
+    }
+
+    #[test]
+    fn is_resolvable() {
+        let mut parser = PacParser::with_resolver(MockResolver(vec![IpAddr::V4(Ipv4Addr::new(
+            127, 0, 0, 1,
+        ))]))
+        .unwrap()
+        .with_bypass(Bypass::new());
+        let pac = pac!(r#"return isResolvable(host) ? "PROXY 1:80" : "DIRECT";"#);
+        let mut pac = parser.load(pac).unwrap();
+        let proxy = pac
+            .find_proxy(&Url::parse("http://localhost").unwrap())
+            .unwrap();
+
+        assert_eq!(
+            proxy,
+            vec![ProxyEntry::Proxied {
+                ty: ProxyType::Proxy,
+                host: "1".into(),
+                port: "80".into()
+            }]
+        );
+    }
 }
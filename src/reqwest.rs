@@ -0,0 +1,90 @@
+//! Converts [`ProxyEntry`] lists into proxy configuration for the
+//! [`reqwest`](::reqwest) HTTP client. Enable with the `reqwest` feature.
+
+use std::sync::Arc;
+
+use url::Url;
+
+use crate::{Error, PacParser, ProxyEntry, ProxyType, Result};
+
+impl ProxyType {
+    fn scheme(self) -> &'static str {
+        match self {
+            ProxyType::Proxy | ProxyType::Http => "http",
+            ProxyType::Https => "https",
+            ProxyType::Socks => "socks5",
+            ProxyType::Socks4 => "socks4",
+            ProxyType::Socks5 => "socks5",
+        }
+    }
+}
+
+impl ProxyEntry {
+    /// Builds the `scheme://host:port` URL reqwest expects for this entry,
+    /// or `None` for [`ProxyEntry::Direct`] and for entries whose host and
+    /// port do not form a valid URL.
+    pub fn reqwest_url(&self) -> Option<Url> {
+        match self {
+            ProxyEntry::Direct => None,
+            ProxyEntry::Proxied { ty, host, port } => {
+                Url::parse(&format!("{}://{host}:{port}", ty.scheme())).ok()
+            }
+        }
+    }
+}
+
+fn to_proxy(entry: &ProxyEntry) -> Result<::reqwest::Proxy> {
+    // DIRECT entries are filtered out before this point, so `None` here
+    // means the entry's host and port do not form a valid URL.
+    let url = entry
+        .reqwest_url()
+        .ok_or_else(|| Error::MalformedProxyEntry(format!("{entry:?}")))?;
+
+    Ok(::reqwest::Proxy::all(url)?)
+}
+
+/// Splits a PAC result into the first usable [`reqwest::Proxy`](::reqwest::Proxy)
+/// and the remaining fallbacks, since `reqwest::Client` only accepts a
+/// single proxy per rule rather than an ordered fallback chain. The first
+/// slot is `None` when `entries` is empty or starts with `DIRECT`.
+pub fn first_proxy(entries: &[ProxyEntry]) -> Result<(Option<::reqwest::Proxy>, Vec<::reqwest::Proxy>)> {
+    let mut entries = entries.iter();
+
+    let first = match entries.next() {
+        None | Some(ProxyEntry::Direct) => None,
+        Some(entry) => Some(to_proxy(entry)?),
+    };
+
+    let fallbacks = entries
+        .filter(|entry| !matches!(entry, ProxyEntry::Direct))
+        .map(to_proxy)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((first, fallbacks))
+}
+
+/// Builds a [`reqwest::Proxy`](::reqwest::Proxy) that re-evaluates
+/// `pac_source` for every request and proxies through whatever
+/// `FindProxyForURL` returns, falling back to `DIRECT` when the first entry
+/// is direct or nothing resolves.
+///
+/// `PacParser` wraps a boa JS context that is `!Send`, so it can't be shared
+/// across threads behind a `Mutex` the way `reqwest::Proxy::custom`'s
+/// `Send + Sync` bound would require. Instead, `new_parser` is called once
+/// per request to build a fresh `PacParser` that stays on the thread
+/// evaluating it.
+pub fn custom_proxy(
+    new_parser: impl Fn() -> PacParser + Send + Sync + 'static,
+    pac_source: Arc<str>,
+) -> ::reqwest::Proxy {
+    ::reqwest::Proxy::custom(move |url| {
+        let mut parser = new_parser();
+        let mut pac = parser.load(&*pac_source).ok()?;
+        let entries = pac.find_proxy(url).ok()?;
+
+        match entries.first() {
+            None | Some(ProxyEntry::Direct) => None,
+            Some(_) => entries.iter().find_map(ProxyEntry::reqwest_url),
+        }
+    })
+}